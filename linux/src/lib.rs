@@ -1,9 +1,56 @@
 use log::{info, warn, debug, error};
-use nix::{sys::{ptrace, wait}, unistd::Pid};
-use std::fs::{File, read_to_string};
-use std::io::{Read, Seek, SeekFrom};
+use nix::{sys::{ptrace, uio::{process_vm_readv, RemoteIoVec}, wait}, unistd::Pid};
+use std::ffi::c_void;
+use std::fs::{File, read_dir, read_to_string};
+use std::io::{IoSliceMut, Read, Seek, SeekFrom, Write};
 use std::mem::MaybeUninit;
-use anyhow::Result;
+use std::os::raw::c_long;
+use anyhow::{anyhow, Result};
+use libc::{iovec, user_regs_struct};
+
+mod minidump;
+mod scan;
+
+pub use scan::ScanConfig;
+
+/// `NT_PRSTATUS`, the `PTRACE_GETREGSET` note type for general-purpose
+/// registers.
+const NT_PRSTATUS: libc::c_int = 1;
+
+/// a thread's general-purpose register file, as fetched via
+/// `PTRACE_GETREGSET`/`NT_PRSTATUS`.
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadRegisters {
+    pub tid: i32,
+    pub regs: user_regs_struct,
+}
+
+/// a method of reading a target's memory, in the order they're tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReadStrategy {
+    /// a single `process_vm_readv(2)` call; can scatter/gather across
+    /// regions in one round trip, so this is tried first.
+    VirtualMem,
+
+    /// seek + read on `/proc/<pid>/mem`.
+    File,
+
+    /// `PTRACE_PEEKDATA`, word by word; slow but works whenever the target
+    /// can be ptraced at all, so it's the last resort.
+    Ptrace,
+}
+
+impl ReadStrategy {
+    /// tried in this order on the first read; see [`Mem::read_bytes`].
+    const ALL: [ReadStrategy; 3] = [ReadStrategy::VirtualMem, ReadStrategy::File, ReadStrategy::Ptrace];
+}
+
+/// bit 63 of a `/proc/<pid>/pagemap` entry: page is present in RAM.
+const PAGEMAP_PRESENT: u64 = 1 << 63;
+
+/// bit 62 of a `/proc/<pid>/pagemap` entry: page is swapped out.
+#[allow(dead_code)]
+const PAGEMAP_SWAPPED: u64 = 1 << 62;
 
 /// for /proc/<pid>/maps
 #[derive(Debug)]
@@ -42,6 +89,18 @@ pub struct Mem {
 
     /// vector holding the entire virtual addr mappings from /proc/pid/maps
     mapping:   Vec<Maps>,
+
+    /// the read method that last succeeded, so later reads skip the probing.
+    read_strategy: Option<ReadStrategy>,
+
+    /// disassemble executable mappings instead of scanning them for strings.
+    disasm: bool,
+
+    /// which mappings to scan for strings, and how.
+    scan_config: ScanConfig,
+
+    /// general-purpose registers of every thread captured on the last dump.
+    threads: Vec<ThreadRegisters>,
 }
 
 impl Mem {
@@ -49,13 +108,30 @@ impl Mem {
         Ok(Self {
             pid: Pid::from_raw(0),
             mapping: Vec::new(),
+            read_strategy: None,
+            disasm: false,
+            scan_config: ScanConfig::default(),
+            threads: Vec::new(),
         })
     }
 
+    /// registers captured for every thread of the target on the last dump.
+    pub fn threads(&self) -> &[ThreadRegisters] {
+        &self.threads
+    }
+
     pub fn set_pid(&mut self, _pid: i32) {
         self.pid = Pid::from_raw(_pid);
     }
 
+    pub fn set_disasm(&mut self, enabled: bool) {
+        self.disasm = enabled;
+    }
+
+    pub fn set_scan_config(&mut self, config: ScanConfig) {
+        self.scan_config = config;
+    }
+
     /// dump memory of linux process
     pub fn dump(&mut self) -> Result<()> {
         ptrace::attach(self.pid).expect("failed to attach to pid.");
@@ -66,13 +142,22 @@ impl Mem {
 
                 // read from /proc/pid/maps and put into self.mapping
                 self.load_mapping();
-                    
+
                 // create a copy of the mapping
                 let old_mapping: Vec<Maps> = std::mem::take(&mut self.mapping);
 
+                // register state for every thread, for context on the dump
+                // (locating the live stack, current pc into the disasm).
+                // every other thread stays stopped (attached) until the
+                // memory read below is done, so it can't mutate the memory
+                // we're about to snapshot.
+                let attached_threads = self.capture_thread_registers();
+
                 // read from /proc/pid/mem
                 self.read_memory(&old_mapping);
 
+                Mem::detach_threads(&attached_threads);
+
                 // restore the old mapping and detach from the ptrace
                 self.mapping = old_mapping;
                 ptrace::detach(self.pid, None).expect("failed to detach pid.");
@@ -85,7 +170,78 @@ impl Mem {
         };
 
         Ok(())
-    }    
+    }
+
+    /// dump memory of the linux process to a Windows-format minidump
+    /// (`.dmp`) file at `path`, instead of logging strings.
+    pub fn dump_minidump(&mut self, path: &str) -> Result<()> {
+        ptrace::attach(self.pid).expect("failed to attach to pid.");
+
+        match wait::waitpid(self.pid, None) {
+            Ok(wait::WaitStatus::Stopped(_, _)) => {
+                info!("ptrace::attach({})", self.pid);
+
+                self.load_mapping();
+                let old_mapping: Vec<Maps> = std::mem::take(&mut self.mapping);
+
+                let regions = self.collect_memory_regions(&old_mapping);
+                let modules = Mem::collect_modules(&old_mapping);
+                let is_64_bit = self.target_is_64_bit();
+                minidump::write(path, &regions, &modules, is_64_bit)?;
+                info!("wrote minidump to {}", path);
+
+                self.mapping = old_mapping;
+                ptrace::detach(self.pid, None).expect("failed to detach pid.");
+                info!("ptrace::detach({})", self.pid);
+            },
+
+            Err(e) => error!("waitpid error {}", e),
+
+            _ => ()
+        };
+
+        Ok(())
+    }
+
+    /// gather the readable, resident bytes of every mapping, the same way
+    /// `read_memory` does for its string scan.
+    fn collect_memory_regions(&mut self, mapping: &[Maps]) -> Vec<minidump::MemoryRegion> {
+        let mut regions = Vec::new();
+
+        for map in mapping.iter() {
+            if !map.r {
+                continue;
+            }
+
+            for (chunk_start, chunk_end) in self.present_ranges(map.start, map.end) {
+                let num_elems = chunk_end - chunk_start;
+                match self.read_mem_slice::<u8>(chunk_start as u64, num_elems, 0) {
+                    Ok(data) => regions.push(minidump::MemoryRegion { start: chunk_start as u64, data }),
+                    Err(e) => warn!("failed to read 0x{:x}-0x{:x} for minidump: {}", chunk_start, chunk_end, e),
+                }
+            }
+        }
+
+        regions
+    }
+
+    /// build the `ModuleListStream` entries from the file-backed mappings.
+    fn collect_modules(mapping: &[Maps]) -> Vec<minidump::ModuleInfo> {
+        mapping.iter()
+            .filter_map(|map| {
+                let path = map.pathname.clone()?;
+                if path.is_empty() || path.starts_with('[') {
+                    return None;
+                }
+
+                Some(minidump::ModuleInfo {
+                    base: map.start as u64,
+                    size: (map.end - map.start) as u32,
+                    path,
+                })
+            })
+            .collect()
+    }
 
     /// parse data from /proc/<pid>/maps
     fn load_mapping(&mut self) {
@@ -130,77 +286,399 @@ impl Mem {
         self.mapping = mapping;
     }
 
+    /// capture general-purpose registers for every thread of `self.pid` by
+    /// enumerating `/proc/<pid>/task/`, attaching to every thread other
+    /// than the main one.
+    ///
+    /// every thread this attaches is left stopped and returned to the
+    /// caller, which must pass it to [`Mem::detach_threads`] once it's
+    /// done reading memory -- detaching here would let those threads run
+    /// free and mutate the process while the memory snapshot is taken,
+    /// defeating the whole point of capturing register state alongside it.
+    fn capture_thread_registers(&mut self) -> Vec<Pid> {
+        let mut threads = Vec::new();
+        let mut attached = Vec::new();
+
+        for tid in Mem::enumerate_threads(self.pid) {
+            let thread_pid = Pid::from_raw(tid);
+
+            if thread_pid != self.pid {
+                if let Err(e) = ptrace::attach(thread_pid) {
+                    warn!("failed to attach to thread {}: {}", tid, e);
+                    continue;
+                }
+                attached.push(thread_pid);
+                if let Err(e) = wait::waitpid(thread_pid, None) {
+                    warn!("failed to wait for thread {}: {}", tid, e);
+                    continue;
+                }
+            }
+
+            match Mem::getregset(tid) {
+                Ok(regs) => {
+                    info!("thread {}: rip={:#x} rsp={:#x}", tid, regs.rip, regs.rsp);
+                    threads.push(ThreadRegisters { tid, regs });
+                }
+                Err(e) => warn!("failed to capture registers for thread {}: {}", tid, e),
+            }
+        }
+
+        self.threads = threads;
+        attached
+    }
+
+    /// detach every thread previously attached by [`Mem::capture_thread_registers`].
+    /// best-effort: a failure to detach one thread shouldn't stop the rest
+    /// from being released.
+    fn detach_threads(attached: &[Pid]) {
+        for &thread_pid in attached {
+            if let Err(e) = ptrace::detach(thread_pid, None) {
+                warn!("failed to detach thread {}: {}", thread_pid, e);
+            }
+        }
+    }
+
+    /// list every tid under `/proc/<pid>/task/`, falling back to just the
+    /// main thread if that can't be read.
+    fn enumerate_threads(pid: Pid) -> Vec<i32> {
+        match read_dir(format!("/proc/{}/task", pid)) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().to_str().and_then(|s| s.parse::<i32>().ok()))
+                .collect(),
+            Err(e) => {
+                warn!("failed to enumerate threads of pid {}, using main thread only: {}", pid, e);
+                vec![pid.as_raw()]
+            }
+        }
+    }
+
+    /// `PTRACE_GETREGSET`/`NT_PRSTATUS` via an `iovec`, fetching the
+    /// general-purpose register file directly (nix has no safe wrapper for
+    /// arbitrary regset types, only the legacy `PTRACE_GETREGS`).
+    fn getregset(tid: i32) -> Result<user_regs_struct> {
+        let mut regs: MaybeUninit<user_regs_struct> = MaybeUninit::uninit();
+        let mut iov = iovec {
+            iov_base: regs.as_mut_ptr() as *mut c_void,
+            iov_len: core::mem::size_of::<user_regs_struct>(),
+        };
+
+        let ret = unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETREGSET,
+                tid,
+                NT_PRSTATUS as *mut c_void,
+                &mut iov as *mut iovec as *mut c_void,
+            )
+        };
+
+        if ret < 0 {
+            return Err(anyhow!(
+                "PTRACE_GETREGSET failed for tid {}: {}", tid, std::io::Error::last_os_error()
+            ));
+        }
+
+        Ok(unsafe { regs.assume_init() })
+    }
+
     /// read a `T` from memory at `start_addr`
     fn read_memory(&mut self, mapping: &[Maps]) {
+        let mut records = Vec::new();
+
         // iter over the mapping and find strings
         for map in mapping.iter() {
             // only continue if readable
             if !map.r {
                 continue;
             }
-            
-            // dont read these
-            if let Some(file) = &map.pathname {
-                if file.starts_with("/usr") {
-                    continue;
-                }
+
+            // region filtering: permission mask and pathname globs, in
+            // place of the old hard-coded "/usr" skip
+            if !self.scan_config.matches_perms(map.r, map.w, map.x) {
+                continue;
+            }
+            if !self.scan_config.matches_path(map.pathname.as_deref()) {
+                continue;
             }
 
             // for logging
             Mem::display_mapping(map);
 
-            // read the entire memory region
-            let num_elems = map.end - map.start;
-            let start_addr = map.start as u64;
-            if let Ok(data) = self.read_mem_slice::<u8>(start_addr, num_elems, 0) {
-                let mut i = 0;
-                while i < data.len() {
-                    // check valid ascii
-                    let string_start = i;
-                    let mut string_len = 0;
-                    while i < data.len() && data[i].is_ascii() && data[i] >= 32 && data[i] <= 126 {
-                        string_len += 1;
-                        i += 1;
+            // only read pages that are actually resident, skipping guard
+            // pages, reserved-but-unfaulted pages, and swapped-out pages
+            let present = self.present_ranges(map.start, map.end);
+            if present.len() != 1 || present[0] != (map.start, map.end) {
+                debug!("0x{:x}-0x{:x}: {} resident chunk(s) (of {:x} total)",
+                    map.start, map.end, present.len(), map.end - map.start);
+            }
+
+            for (chunk_start, chunk_end) in present {
+                let num_elems = chunk_end - chunk_start;
+                let start_addr = chunk_start as u64;
+                if let Ok(data) = self.read_mem_slice::<u8>(start_addr, num_elems, 0) {
+                    if map.x && self.disasm {
+                        self.disassemble_region(start_addr, &data);
+                        continue;
                     }
 
-                    // check length 
-                    if string_len >= 4 {
-                        let string_data = String::from_utf8_lossy(&data[string_start..string_start + string_len]);
-                        info!("0x{:x}: {}", start_addr + string_start as u64, string_data);
+                    // scan this chunk on its own so a hole between resident
+                    // runs never joins two strings together
+                    let min_len = self.scan_config.min_len;
+                    let mut found = scan::scan_ascii(&data, start_addr, min_len);
+                    if self.scan_config.utf16 {
+                        found.extend(scan::scan_utf16(&data, start_addr, min_len));
                     }
 
-                    if i == string_start {
-                        i += 1;
+                    for record in &found {
+                        info!("0x{:x}: [{:?}] {}", record.address, record.encoding, record.text);
                     }
+                    records.extend(found);
+                }
+            }
+        }
+
+        if let Some(path) = &self.scan_config.output {
+            if let Err(e) = Mem::write_ndjson(path, &records) {
+                error!("failed to write scan output to {}: {}", path, e);
+            }
+        }
+    }
+
+    /// write `records` as newline-delimited JSON to `path`.
+    fn write_ndjson(path: &str, records: &[scan::StringRecord]) -> Result<()> {
+        let mut file = File::create(path)?;
+        for record in records {
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+        }
+        Ok(())
+    }
+
+    /// disassemble `data` (read from `start_addr`) and log each decoded
+    /// instruction, picking a 32- vs 64-bit decoder based on the target.
+    fn disassemble_region(&self, start_addr: u64, data: &[u8]) {
+        use yaxpeax_x86::{amd64, protected_mode};
+
+        if self.target_is_64_bit() {
+            Mem::disassemble_with(amd64::InstDecoder::default(), start_addr, data);
+        } else {
+            Mem::disassemble_with(protected_mode::InstDecoder::default(), start_addr, data);
+        }
+    }
+
+    /// decode `data` one instruction at a time with `decoder` and log each
+    /// one, resynchronizing past a single byte on a decode error so one
+    /// bad byte doesn't abort the whole region. shared by the 32- and
+    /// 64-bit paths in [`Mem::disassemble_region`] -- they only differ in
+    /// which `yaxpeax_x86` decoder they hand in.
+    fn disassemble_with<A, D>(decoder: D, start_addr: u64, data: &[u8])
+    where
+        A: yaxpeax_arch::Arch,
+        A::Instruction: core::fmt::Display,
+        D: yaxpeax_arch::Decoder<A>,
+    {
+        use yaxpeax_arch::{Decoder as _, LengthedInstruction, U8Reader};
+
+        let mut offset = 0usize;
+
+        while offset < data.len() {
+            let mut reader = U8Reader::new(&data[offset..]);
+            match decoder.decode(&mut reader) {
+                Ok(inst) => {
+                    info!("0x{:x}: {}", start_addr + offset as u64, inst);
+                    offset += (inst.len().to_const() as usize).max(1);
                 }
+                Err(_) => offset += 1,
             }
         }
     }
 
+    /// does the target look like a 64-bit ELF binary? inspects the `EI_CLASS`
+    /// byte of its executable's ELF header via `/proc/<pid>/exe`, defaulting
+    /// to 64-bit if that can't be determined.
+    fn target_is_64_bit(&self) -> bool {
+        const ELFCLASS64: u8 = 2;
+
+        let mut header = [0u8; 5];
+        match File::open(format!("/proc/{}/exe", self.pid)).and_then(|mut f| f.read_exact(&mut header)) {
+            Ok(()) => header[4] == ELFCLASS64,
+            Err(_) => true,
+        }
+    }
+
+    /// consult `/proc/<pid>/pagemap` and return the `[start, end)` sub-ranges
+    /// of `[start, end)` whose pages are actually resident, coalescing runs
+    /// of present pages into contiguous chunks.
+    ///
+    /// if the pagemap can't be read (e.g. insufficient privilege), falls
+    /// back to treating the whole span as present, matching the previous
+    /// unconditional `read_exact` behavior.
+    fn present_ranges(&self, start: usize, end: usize) -> Vec<(usize, usize)> {
+        let mut pagemap = match File::open(format!("/proc/{}/pagemap", self.pid)) {
+            Ok(f) => f,
+            Err(e) => {
+                warn!("failed to open pagemap for pid {}, reading whole region: {}", self.pid, e);
+                return vec![(start, end)];
+            }
+        };
+
+        Mem::coalesce_present_ranges(&mut pagemap, start, end, Mem::page_size())
+    }
+
+    /// walk `[start, end)` page by page, consulting `pagemap` for each one,
+    /// and coalesce consecutive resident pages into `[start, end)` chunks.
+    /// split out of [`Mem::present_ranges`] so the coalescing logic can be
+    /// tested against a stubbed pagemap file, without a real traced process.
+    fn coalesce_present_ranges(pagemap: &mut File, start: usize, end: usize, page_size: usize) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::new();
+        let mut run_start: Option<usize> = None;
+        let mut page = start;
+
+        while page < end {
+            let resident = match Mem::pagemap_entry(pagemap, page, page_size) {
+                Ok(entry) => entry & PAGEMAP_PRESENT != 0,
+                Err(e) => {
+                    warn!("failed to read pagemap entry at {:x}: {}", page, e);
+                    false
+                }
+            };
+
+            match (resident, run_start) {
+                (true, None) => run_start = Some(page),
+                (false, Some(s)) => {
+                    ranges.push((s, page));
+                    run_start = None;
+                }
+                _ => (),
+            }
+
+            page += page_size;
+        }
+
+        if let Some(s) = run_start {
+            ranges.push((s, end));
+        }
+
+        ranges
+    }
+
+    /// read the single 64-bit pagemap entry covering `vaddr`.
+    fn pagemap_entry(pagemap: &mut File, vaddr: usize, page_size: usize) -> Result<u64> {
+        let offset = (vaddr / page_size) as u64 * 8;
+        pagemap.seek(SeekFrom::Start(offset))?;
+
+        let mut buf = [0u8; 8];
+        pagemap.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn page_size() -> usize {
+        nix::unistd::sysconf(nix::unistd::SysconfVar::PAGE_SIZE)
+            .ok()
+            .flatten()
+            .unwrap_or(4096) as usize
+    }
+
     // read a `T` from memory
     fn read_mem_slice<T: Pod>(&mut self, start_addr: u64, num_elems: usize, offset: usize) -> Result<Box<[T]>> {
         // reserve uninitialized (MaybeUninit) space for `num_elems` amount of T on the heap (Box)
         let num_elems = num_elems.saturating_sub(offset);
         let mut ret: Box<[MaybeUninit<T>]> = Box::new_uninit_slice(num_elems);
 
-        // go to start of addr block
-        let mut mem_file = File::open(format!("/proc/{}/mem", self.pid))?;
-        mem_file.seek(SeekFrom::Start(start_addr))?;
-        
         // create an empty byte slice that points to ret
         let ptr = unsafe {
             core::slice::from_raw_parts_mut(
                 ret.as_mut_ptr() as *mut u8, core::mem::size_of_val(&*ret))
         };
 
-        // fill the byte slice
-        match mem_file.read_exact(ptr) {
+        match self.read_bytes(start_addr, ptr) {
             Ok(()) => Ok(unsafe { ret.assume_init() }),
             Err(e) => {
                 warn!("Failed to read memory at {:x} (+{:x}): {}", start_addr, offset, e);
-                Err(e.into())
+                Err(e)
+            }
+        }
+    }
+
+    /// fill `buf` with memory starting at `start_addr`, using whichever
+    /// access method already proved to work (see [`ReadStrategy`]), or
+    /// probing all of them in order on the first call and caching the
+    /// winner on `self.read_strategy`.
+    fn read_bytes(&mut self, start_addr: u64, buf: &mut [u8]) -> Result<()> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(strategy) = self.read_strategy {
+            return self.read_bytes_with(strategy, start_addr, buf);
+        }
+
+        let mut errors = Vec::new();
+        for strategy in ReadStrategy::ALL {
+            match self.read_bytes_with(strategy, start_addr, buf) {
+                Ok(()) => {
+                    debug!("using {:?} to read memory of pid {}", strategy, self.pid);
+                    self.read_strategy = Some(strategy);
+                    return Ok(());
+                }
+                Err(e) => errors.push(format!("{:?}: {}", strategy, e)),
             }
         }
+
+        Err(anyhow!("all memory read strategies failed: {}", errors.join("; ")))
+    }
+
+    fn read_bytes_with(&self, strategy: ReadStrategy, start_addr: u64, buf: &mut [u8]) -> Result<()> {
+        match strategy {
+            ReadStrategy::VirtualMem => self.read_via_process_vm_readv(start_addr, buf),
+            ReadStrategy::File       => self.read_via_proc_mem(start_addr, buf),
+            ReadStrategy::Ptrace     => self.read_via_ptrace(start_addr, buf),
+        }
+    }
+
+    /// `process_vm_readv(2)`: one syscall can scatter/gather across
+    /// multiple regions, far faster than seeking the proc file per region.
+    fn read_via_process_vm_readv(&self, start_addr: u64, buf: &mut [u8]) -> Result<()> {
+        let len = buf.len();
+        let bytes_read = process_vm_readv(
+            self.pid,
+            &mut [IoSliceMut::new(buf)],
+            &[RemoteIoVec { base: start_addr as usize, len }],
+        )?;
+
+        if bytes_read != len {
+            return Err(anyhow!("short read: got {} of {} bytes", bytes_read, len));
+        }
+
+        Ok(())
+    }
+
+    /// seek + `read_exact` on `/proc/<pid>/mem`.
+    fn read_via_proc_mem(&self, start_addr: u64, buf: &mut [u8]) -> Result<()> {
+        let mut mem_file = File::open(format!("/proc/{}/mem", self.pid))?;
+        mem_file.seek(SeekFrom::Start(start_addr))?;
+        mem_file.read_exact(buf)?;
+        Ok(())
+    }
+
+    /// last resort: `PTRACE_PEEKDATA`, one word at a time.
+    fn read_via_ptrace(&self, start_addr: u64, buf: &mut [u8]) -> Result<()> {
+        let word_size = core::mem::size_of::<c_long>();
+        let mut addr = start_addr;
+        let mut filled = 0;
+
+        while filled < buf.len() {
+            let word = ptrace::read(self.pid, addr as *mut c_void)
+                .map_err(|e| anyhow!("PTRACE_PEEKDATA at {:x}: {}", addr, e))?;
+            let word_bytes = word.to_ne_bytes();
+
+            let take = word_size.min(buf.len() - filled);
+            buf[filled..filled + take].copy_from_slice(&word_bytes[..take]);
+
+            filled += take;
+            addr += word_size as u64;
+        }
+
+        Ok(())
     }
 
     fn display_mapping(mapping: &Maps) {
@@ -255,3 +733,65 @@ unsafe impl<const N: usize> Pod for [u128;  N] {}
 unsafe impl<const N: usize> Pod for [usize; N] {}
 unsafe impl<const N: usize> Pod for [f32;   N] {}
 unsafe impl<const N: usize> Pod for [f64;   N] {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    /// write `entries` (one `u64` pagemap entry per page, in order) to a
+    /// tempfile standing in for `/proc/<pid>/pagemap`, identified by `name`
+    /// so concurrently-running tests don't clobber each other's file.
+    fn stub_pagemap(name: &str, entries: &[u64]) -> File {
+        let path = format!("{}/seer-pagemap-test-{}-{}.bin", std::env::temp_dir().display(), std::process::id(), name);
+
+        let mut file = File::create(&path).unwrap();
+        for entry in entries {
+            file.write_all(&entry.to_le_bytes()).unwrap();
+        }
+
+        let file = File::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        file
+    }
+
+    #[test]
+    fn coalesce_present_ranges_merges_adjacent_present_pages() {
+        let page_size = 0x1000;
+        let mut pagemap = stub_pagemap("merge", &[PAGEMAP_PRESENT, PAGEMAP_PRESENT, PAGEMAP_PRESENT]);
+
+        let ranges = Mem::coalesce_present_ranges(&mut pagemap, 0, 3 * page_size, page_size);
+
+        assert_eq!(ranges, vec![(0, 3 * page_size)]);
+    }
+
+    #[test]
+    fn coalesce_present_ranges_splits_on_single_absent_page() {
+        let page_size = 0x1000;
+        let mut pagemap = stub_pagemap("split", &[PAGEMAP_PRESENT, 0, PAGEMAP_PRESENT]);
+
+        let ranges = Mem::coalesce_present_ranges(&mut pagemap, 0, 3 * page_size, page_size);
+
+        assert_eq!(ranges, vec![(0, page_size), (2 * page_size, 3 * page_size)]);
+    }
+
+    #[test]
+    fn coalesce_present_ranges_handles_leading_and_trailing_absent() {
+        let page_size = 0x1000;
+        let mut pagemap = stub_pagemap("edges", &[0, PAGEMAP_PRESENT, 0]);
+
+        let ranges = Mem::coalesce_present_ranges(&mut pagemap, 0, 3 * page_size, page_size);
+
+        assert_eq!(ranges, vec![(page_size, 2 * page_size)]);
+    }
+
+    #[test]
+    fn coalesce_present_ranges_all_absent_returns_empty() {
+        let page_size = 0x1000;
+        let mut pagemap = stub_pagemap("empty", &[0, 0, 0]);
+
+        let ranges = Mem::coalesce_present_ranges(&mut pagemap, 0, 3 * page_size, page_size);
+
+        assert!(ranges.is_empty());
+    }
+}
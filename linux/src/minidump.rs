@@ -0,0 +1,247 @@
+//! A minimal Windows-format minidump (`.dmp`) writer.
+//!
+//! Only the streams `seer` actually populates are modeled here --
+//! `MemoryListStream`, `ModuleListStream`, and `SystemInfoStream`. Anything
+//! else in the format (exception stream, thread list, ...) is simply
+//! absent from the stream directory; readers that understand minidumps
+//! tolerate a sparse directory fine.
+
+use anyhow::Result;
+use std::fs::File;
+use std::io::Write;
+
+const SIGNATURE: u32 = 0x504d_444d; // "MDMP"
+const VERSION: u32 = 42899;
+
+const STREAM_MODULE_LIST: u32 = 4;
+const STREAM_MEMORY_LIST: u32 = 5;
+const STREAM_SYSTEM_INFO: u32 = 7;
+
+const HEADER_SIZE: u32 = 32;
+const DIRECTORY_ENTRY_SIZE: u32 = 12;
+const STREAM_COUNT: u32 = 3;
+
+/// a region of memory to embed in the `MemoryListStream`.
+pub struct MemoryRegion {
+    pub start: u64,
+    pub data: Box<[u8]>,
+}
+
+/// a file-backed mapping to embed in the `ModuleListStream`.
+pub struct ModuleInfo {
+    pub base: u64,
+    pub size: u32,
+    pub path: String,
+}
+
+/// write a minidump containing `regions` and `modules` to `path`. `is_64_bit`
+/// selects the `ProcessorArchitecture` reported in the `SystemInfoStream`,
+/// mirroring the bitness `Mem::target_is_64_bit()` picked for disassembly.
+pub fn write(path: &str, regions: &[MemoryRegion], modules: &[ModuleInfo], is_64_bit: bool) -> Result<()> {
+    // streams sit back to back right after the directory; track each
+    // one's rva as we go so the directory entries can point at them.
+    let directory_rva = HEADER_SIZE;
+    let mut cursor = directory_rva + DIRECTORY_ENTRY_SIZE * STREAM_COUNT;
+
+    let system_info = system_info_stream(is_64_bit);
+    let system_info_rva = cursor;
+    cursor += system_info.len() as u32;
+
+    let (module_list, module_names) = module_list_stream(modules, cursor);
+    let module_list_rva = cursor;
+    cursor += (module_list.len() + module_names.len()) as u32;
+
+    let memory_list_rva = cursor;
+    let memory_list_header_size = 4 + regions.len() as u32 * 16;
+    let mut memory_data_rva = memory_list_rva + memory_list_header_size;
+    let mut memory_list = Vec::with_capacity(memory_list_header_size as usize);
+    memory_list.extend_from_slice(&(regions.len() as u32).to_le_bytes());
+    for region in regions {
+        memory_list.extend_from_slice(&region.start.to_le_bytes());
+        memory_list.extend_from_slice(&(region.data.len() as u32).to_le_bytes());
+        memory_list.extend_from_slice(&memory_data_rva.to_le_bytes());
+        memory_data_rva += region.data.len() as u32;
+    }
+
+    let mut buf = Vec::new();
+
+    // MINIDUMP_HEADER
+    buf.extend_from_slice(&SIGNATURE.to_le_bytes());
+    buf.extend_from_slice(&VERSION.to_le_bytes());
+    buf.extend_from_slice(&STREAM_COUNT.to_le_bytes());
+    buf.extend_from_slice(&directory_rva.to_le_bytes());
+    buf.extend_from_slice(&0u32.to_le_bytes()); // CheckSum
+    buf.extend_from_slice(&0u32.to_le_bytes()); // TimeDateStamp
+    buf.extend_from_slice(&0u64.to_le_bytes()); // Flags (MiniDumpNormal)
+
+    // stream directory (array of MINIDUMP_DIRECTORY)
+    write_directory_entry(&mut buf, STREAM_SYSTEM_INFO, system_info.len() as u32, system_info_rva);
+    write_directory_entry(&mut buf, STREAM_MODULE_LIST, (module_list.len() + module_names.len()) as u32, module_list_rva);
+    write_directory_entry(&mut buf, STREAM_MEMORY_LIST, memory_list.len() as u32, memory_list_rva);
+
+    buf.extend_from_slice(&system_info);
+    buf.extend_from_slice(&module_list);
+    buf.extend_from_slice(&module_names);
+    buf.extend_from_slice(&memory_list);
+    for region in regions {
+        buf.extend_from_slice(&region.data);
+    }
+
+    File::create(path)?.write_all(&buf)?;
+    Ok(())
+}
+
+fn write_directory_entry(buf: &mut Vec<u8>, stream_type: u32, data_size: u32, rva: u32) {
+    buf.extend_from_slice(&stream_type.to_le_bytes());
+    buf.extend_from_slice(&data_size.to_le_bytes());
+    buf.extend_from_slice(&rva.to_le_bytes());
+}
+
+/// `MINIDUMP_SYSTEM_INFO`, filled in with just enough to identify the
+/// target as an x86 or x86-64 process running on Linux.
+fn system_info_stream(is_64_bit: bool) -> Vec<u8> {
+    // PROCESSOR_ARCHITECTURE_AMD64 or PROCESSOR_ARCHITECTURE_INTEL
+    let processor_architecture: u16 = if is_64_bit { 9 } else { 0 };
+
+    let mut buf = Vec::with_capacity(56);
+    buf.extend_from_slice(&processor_architecture.to_le_bytes());
+    buf.extend_from_slice(&0u16.to_le_bytes()); // ProcessorLevel
+    buf.extend_from_slice(&0u16.to_le_bytes()); // ProcessorRevision
+    buf.push(1); // NumberOfProcessors
+    buf.push(0); // ProductType
+    buf.extend_from_slice(&0u32.to_le_bytes()); // MajorVersion
+    buf.extend_from_slice(&0u32.to_le_bytes()); // MinorVersion
+    buf.extend_from_slice(&0u32.to_le_bytes()); // BuildNumber
+    buf.extend_from_slice(&0x8201u32.to_le_bytes()); // PlatformId: MD_OS_LINUX, as used by breakpad/crashpad for Linux targets
+    buf.extend_from_slice(&0u32.to_le_bytes()); // CSDVersionRva
+    buf.extend_from_slice(&0u16.to_le_bytes()); // SuiteMask
+    buf.extend_from_slice(&0u16.to_le_bytes()); // Reserved2
+    buf.extend_from_slice(&[0u8; 24]); // Cpu union, left zeroed
+    buf
+}
+
+/// `MINIDUMP_MODULE_LIST` immediately followed by the `MINIDUMP_STRING`
+/// blobs its entries' `ModuleNameRva` point into.
+fn module_list_stream(modules: &[ModuleInfo], list_rva: u32) -> (Vec<u8>, Vec<u8>) {
+    const MODULE_SIZE: u32 = 108;
+
+    let mut list = Vec::new();
+    list.extend_from_slice(&(modules.len() as u32).to_le_bytes());
+
+    let mut names = Vec::new();
+    let mut name_rva = list_rva + 4 + MODULE_SIZE * modules.len() as u32;
+
+    for module in modules {
+        list.extend_from_slice(&module.base.to_le_bytes());
+        list.extend_from_slice(&module.size.to_le_bytes());
+        list.extend_from_slice(&0u32.to_le_bytes()); // CheckSum
+        list.extend_from_slice(&0u32.to_le_bytes()); // TimeDateStamp
+        list.extend_from_slice(&name_rva.to_le_bytes()); // ModuleNameRva
+        list.extend_from_slice(&[0u8; 52]); // VersionInfo (VS_FIXEDFILEINFO), unused
+        list.extend_from_slice(&[0u8; 8]); // CvRecord
+        list.extend_from_slice(&[0u8; 8]); // MiscRecord
+        list.extend_from_slice(&[0u8; 8]); // Reserved0
+        list.extend_from_slice(&[0u8; 8]); // Reserved1
+
+        let utf16: Vec<u16> = module.path.encode_utf16().collect();
+        names.extend_from_slice(&((utf16.len() as u32) * 2).to_le_bytes());
+        for unit in &utf16 {
+            names.extend_from_slice(&unit.to_le_bytes());
+        }
+        names.extend_from_slice(&0u16.to_le_bytes()); // null terminator
+
+        name_rva += 4 + (utf16.len() as u32 + 1) * 2;
+    }
+
+    (list, names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_u32(buf: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn read_u64(buf: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn read_u16(buf: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes(buf[offset..offset + 2].try_into().unwrap())
+    }
+
+    #[test]
+    fn write_header_and_directory_layout() {
+        let dir = tempfile_path();
+
+        let regions = vec![MemoryRegion { start: 0x1000, data: vec![0xaa, 0xbb, 0xcc].into_boxed_slice() }];
+        let modules = vec![ModuleInfo { base: 0x5000, size: 0x2000, path: "/bin/target".to_string() }];
+
+        write(&dir, &regions, &modules, true).unwrap();
+        let buf = std::fs::read(&dir).unwrap();
+        std::fs::remove_file(&dir).unwrap();
+
+        // MINIDUMP_HEADER
+        assert_eq!(read_u32(&buf, 0), SIGNATURE);
+        assert_eq!(read_u32(&buf, 4), VERSION);
+        assert_eq!(read_u32(&buf, 8), STREAM_COUNT);
+        let directory_rva = read_u32(&buf, 12);
+        assert_eq!(directory_rva, HEADER_SIZE);
+
+        // stream directory: one MINIDUMP_DIRECTORY per stream, in the order written.
+        let entry = |index: u32| directory_rva + DIRECTORY_ENTRY_SIZE * index;
+
+        assert_eq!(read_u32(&buf, entry(0) as usize), STREAM_SYSTEM_INFO);
+        let system_info_rva = read_u32(&buf, entry(0) as usize + 8);
+        assert_eq!(system_info_rva, directory_rva + DIRECTORY_ENTRY_SIZE * STREAM_COUNT);
+        assert_eq!(read_u16(&buf, system_info_rva as usize), 9); // PROCESSOR_ARCHITECTURE_AMD64
+
+        assert_eq!(read_u32(&buf, entry(1) as usize), STREAM_MODULE_LIST);
+        let module_list_size = read_u32(&buf, entry(1) as usize + 4);
+        let module_list_rva = read_u32(&buf, entry(1) as usize + 8);
+        assert_eq!(module_list_rva, system_info_rva + 56);
+
+        assert_eq!(read_u32(&buf, entry(2) as usize), STREAM_MEMORY_LIST);
+        let memory_list_rva = read_u32(&buf, entry(2) as usize + 8);
+        assert_eq!(memory_list_rva, module_list_rva + module_list_size);
+
+        // MINIDUMP_MODULE_LIST: count, then one 108-byte entry per module.
+        assert_eq!(read_u32(&buf, module_list_rva as usize), modules.len() as u32);
+        let module_entry = module_list_rva as usize + 4;
+        assert_eq!(read_u64(&buf, module_entry), modules[0].base);
+        assert_eq!(read_u32(&buf, module_entry + 8), modules[0].size);
+        let name_rva = read_u32(&buf, module_entry + 16);
+        assert_eq!(name_rva, module_list_rva + 4 + 108);
+
+        // MINIDUMP_STRING: byte length, then UTF-16LE units, no null check needed here.
+        let name_len = read_u32(&buf, name_rva as usize);
+        assert_eq!(name_len as usize, modules[0].path.encode_utf16().count() * 2);
+
+        // MINIDUMP_MEMORY_LIST: count, then one MINIDUMP_MEMORY_DESCRIPTOR per region.
+        assert_eq!(read_u32(&buf, memory_list_rva as usize), regions.len() as u32);
+        let descriptor = memory_list_rva as usize + 4;
+        assert_eq!(read_u64(&buf, descriptor), regions[0].start);
+        assert_eq!(read_u32(&buf, descriptor + 8), regions[0].data.len() as u32);
+        let memory_data_rva = read_u32(&buf, descriptor + 12);
+        assert_eq!(&buf[memory_data_rva as usize..memory_data_rva as usize + regions[0].data.len()], &*regions[0].data);
+    }
+
+    #[test]
+    fn write_32_bit_reports_intel_architecture() {
+        let dir = tempfile_path();
+
+        write(&dir, &[], &[], false).unwrap();
+        let buf = std::fs::read(&dir).unwrap();
+        std::fs::remove_file(&dir).unwrap();
+
+        let directory_rva = read_u32(&buf, 12);
+        let system_info_rva = read_u32(&buf, directory_rva as usize + 8);
+        assert_eq!(read_u16(&buf, system_info_rva as usize), 0); // PROCESSOR_ARCHITECTURE_INTEL
+    }
+
+    fn tempfile_path() -> String {
+        format!("{}/seer-minidump-test-{}.dmp", std::env::temp_dir().display(), std::process::id())
+    }
+}
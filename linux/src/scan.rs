@@ -0,0 +1,201 @@
+//! configurable string scanning: which mappings to look at, and which
+//! encodings to look for within them.
+
+use serde::Serialize;
+
+/// which mappings to scan, and how to scan them.
+pub struct ScanConfig {
+    /// permission mask, e.g. `"r-x"`; `-` means "don't care". `None` scans
+    /// every readable mapping, matching the previous hard-coded behavior.
+    pub perms: Option<String>,
+
+    /// glob patterns of mapping paths to scan. empty means "all paths".
+    pub include: Vec<glob::Pattern>,
+
+    /// glob patterns of mapping paths to skip, checked after `include`.
+    pub exclude: Vec<glob::Pattern>,
+
+    /// minimum run length (in characters) to report as a string.
+    pub min_len: usize,
+
+    /// also look for little-endian UTF-16 strings, not just ASCII.
+    pub utf16: bool,
+
+    /// write found strings as newline-delimited JSON to this path.
+    pub output: Option<String>,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            perms: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            min_len: 4,
+            utf16: false,
+            output: None,
+        }
+    }
+}
+
+impl ScanConfig {
+    /// does `(r, w, x)` satisfy the permission mask, if one is set?
+    pub fn matches_perms(&self, r: bool, w: bool, x: bool) -> bool {
+        let Some(mask) = &self.perms else { return true };
+
+        mask.chars().zip([r, w, x]).all(|(want, have)| want == '-' || (want != '-') == have)
+    }
+
+    /// does `path` pass the include/exclude globs?
+    pub fn matches_path(&self, path: Option<&str>) -> bool {
+        let path = path.unwrap_or("");
+
+        if !self.include.is_empty() && !self.include.iter().any(|pat| pat.matches(path)) {
+            return false;
+        }
+
+        !self.exclude.iter().any(|pat| pat.matches(path))
+    }
+}
+
+/// how a string was decoded out of raw memory.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    Ascii,
+    Utf16Le,
+}
+
+/// a string found while scanning a region, in a form that can be printed
+/// or serialized to newline-delimited JSON.
+#[derive(Debug, Serialize)]
+pub struct StringRecord {
+    pub address: u64,
+    pub encoding: Encoding,
+    pub length: usize,
+    pub text: String,
+}
+
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii() && byte >= 32 && byte <= 126
+}
+
+/// find ASCII runs of at least `min_len` printable characters in `data`,
+/// read from `base_addr`.
+pub fn scan_ascii(data: &[u8], base_addr: u64, min_len: usize) -> Vec<StringRecord> {
+    let mut records = Vec::new();
+    let mut i = 0;
+
+    while i < data.len() {
+        let start = i;
+        let mut len = 0;
+        while i < data.len() && is_printable(data[i]) {
+            len += 1;
+            i += 1;
+        }
+
+        if len >= min_len {
+            records.push(StringRecord {
+                address: base_addr + start as u64,
+                encoding: Encoding::Ascii,
+                length: len,
+                text: String::from_utf8_lossy(&data[start..start + len]).into_owned(),
+            });
+        }
+
+        if i == start {
+            i += 1;
+        }
+    }
+
+    records
+}
+
+/// find runs of at least `min_len` little-endian UTF-16 code units (a
+/// printable byte followed by a zero byte) in `data`, read from `base_addr`.
+/// common in Windows-origin data and wide-char buffers.
+pub fn scan_utf16(data: &[u8], base_addr: u64, min_len: usize) -> Vec<StringRecord> {
+    let mut records = Vec::new();
+    let mut i = 0;
+
+    while i + 1 < data.len() {
+        let start = i;
+        let mut units = Vec::new();
+        while i + 1 < data.len() && is_printable(data[i]) && data[i + 1] == 0 {
+            units.push(data[i] as u16);
+            i += 2;
+        }
+
+        if units.len() >= min_len {
+            records.push(StringRecord {
+                address: base_addr + start as u64,
+                encoding: Encoding::Utf16Le,
+                length: units.len(),
+                text: String::from_utf16_lossy(&units),
+            });
+        }
+
+        if i == start {
+            i += 2;
+        }
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_ascii_finds_run_at_min_len() {
+        let data = b"\x00\x00hello\x00\x00";
+        let records = scan_ascii(data, 0x1000, 4);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].address, 0x1002);
+        assert_eq!(records[0].length, 5);
+        assert_eq!(records[0].text, "hello");
+    }
+
+    #[test]
+    fn scan_ascii_skips_runs_shorter_than_min_len() {
+        let data = b"\x00hi\x00ok\x00";
+        let records = scan_ascii(data, 0, 3);
+
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn scan_ascii_finds_multiple_runs() {
+        let data = b"foo\x00bar!";
+        let records = scan_ascii(data, 0, 3);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].text, "foo");
+        assert_eq!(records[1].text, "bar!");
+    }
+
+    #[test]
+    fn scan_utf16_finds_le_pairs() {
+        let mut data = Vec::new();
+        for b in b"hi!!" {
+            data.push(*b);
+            data.push(0);
+        }
+        let records = scan_utf16(&data, 0x2000, 4);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].address, 0x2000);
+        assert_eq!(records[0].length, 4);
+        assert_eq!(records[0].text, "hi!!");
+    }
+
+    #[test]
+    fn scan_utf16_ignores_non_le_bytes() {
+        let data = b"hello world".to_vec();
+        let records = scan_utf16(&data, 0, 4);
+
+        assert!(records.is_empty());
+    }
+}
@@ -6,6 +6,30 @@ use anyhow::Result;
 struct Args {
     #[arg(short, long, help = "PID of target process.")]
     pid: i32,
+
+    #[arg(long, help = "Write a Windows-format minidump (.dmp) to this path instead of logging strings.")]
+    minidump: Option<String>,
+
+    #[arg(long, help = "Disassemble executable mappings instead of scanning them for strings.")]
+    disasm: bool,
+
+    #[arg(long, help = "Only scan mappings whose permissions match this mask, e.g. \"r-x\" ('-' = don't care).")]
+    perms: Option<String>,
+
+    #[arg(long, help = "Glob of mapping paths to scan; repeatable. Default: all paths.")]
+    include: Vec<String>,
+
+    #[arg(long, help = "Glob of mapping paths to skip; repeatable.")]
+    exclude: Vec<String>,
+
+    #[arg(long, default_value_t = 4, help = "Minimum string length to report.")]
+    min_len: usize,
+
+    #[arg(long, help = "Also detect little-endian UTF-16 strings, not just ASCII.")]
+    utf16: bool,
+
+    #[arg(long, help = "Write found strings as newline-delimited JSON to this path.")]
+    output: Option<String>,
 }
 
 fn main() -> Result<()> {
@@ -14,10 +38,23 @@ fn main() -> Result<()> {
 
     let mut mem = Mem::new()?;
     mem.set_pid(args.pid);
-    mem.dump()?;
+    mem.set_disasm(args.disasm);
+    mem.set_scan_config(ScanConfig {
+        perms: args.perms.clone(),
+        include: args.include.iter().map(|p| glob::Pattern::new(p)).collect::<std::result::Result<_, _>>()?,
+        exclude: args.exclude.iter().map(|p| glob::Pattern::new(p)).collect::<std::result::Result<_, _>>()?,
+        min_len: args.min_len,
+        utf16: args.utf16,
+        output: args.output.clone(),
+    });
+
+    match &args.minidump {
+        Some(path) => mem.dump_minidump(path)?,
+        None => mem.dump()?,
+    }
 
     Ok(())
 }
 
 #[cfg(target_os = "linux")]
-use linux::Mem;
+use linux::{Mem, ScanConfig};